@@ -0,0 +1,41 @@
+use anyhow::Result;
+
+use crate::BluetoothDevice;
+
+/// A source of Bluetooth device data and control, abstracting over the platform-specific
+/// mechanism used to reach it (shelling out to macOS tools, a cross-platform BLE stack, ...).
+///
+/// This is the seam that lets bluepods support more than macOS: every command builds on
+/// these four operations, while platform-specific extras (discoverable state, default audio
+/// output, inquiry scans) stay macOS-only for now.
+pub trait BluetoothBackend {
+    /// List all known devices, connected or not, with battery info where available.
+    fn list_devices(&self) -> Result<Vec<BluetoothDevice>>;
+    /// Connect to the device at `address`.
+    fn connect(&self, address: &str) -> Result<()>;
+    /// Disconnect the device at `address`.
+    fn disconnect(&self, address: &str) -> Result<()>;
+    /// Whether the local Bluetooth adapter is powered on.
+    fn power_state(&self) -> Result<bool>;
+}
+
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "macos")]
+pub use macos::MacOsBackend;
+
+#[cfg(not(target_os = "macos"))]
+mod bluest_backend;
+#[cfg(not(target_os = "macos"))]
+pub use bluest_backend::BluestBackend;
+
+/// Select the backend for this platform at compile time.
+#[cfg(target_os = "macos")]
+pub fn default_backend() -> impl BluetoothBackend {
+    MacOsBackend
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn default_backend() -> impl BluetoothBackend {
+    BluestBackend::new()
+}
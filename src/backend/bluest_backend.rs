@@ -0,0 +1,169 @@
+use anyhow::{Context, Result};
+use async_io::Timer;
+use bluest::{Adapter, Device};
+use futures_lite::{future, StreamExt};
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::BluetoothBackend;
+use crate::{BatteryInfo, BluetoothDevice};
+
+/// Standard GATT Battery Service UUID (0x180F).
+const BATTERY_SERVICE: Uuid = Uuid::from_u128(0x0000180f_0000_1000_8000_00805f9b34fb);
+/// Standard GATT Battery Level characteristic UUID (0x2A19).
+const BATTERY_LEVEL: Uuid = Uuid::from_u128(0x00002a19_0000_1000_8000_00805f9b34fb);
+
+/// How long to scan for nearby devices when a target isn't already connected.
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Talks to the Bluetooth stack via `bluest`, the cross-platform BLE crate, so Linux and
+/// Windows users get the same list/connect/disconnect/battery features as macOS. GATT battery
+/// levels are read from the standard Battery Service rather than a vendor-specific report, so
+/// only `single` is populated here (no left/right split as on AirPods-style devices).
+pub struct BluestBackend;
+
+impl BluestBackend {
+    pub fn new() -> Self {
+        BluestBackend
+    }
+
+    async fn adapter() -> Result<Adapter> {
+        let adapter = Adapter::default().await.context("No Bluetooth adapter found")?;
+        adapter.wait_available().await?;
+        Ok(adapter)
+    }
+
+    /// Scan for nearby devices (bonded or not) for up to `DISCOVERY_TIMEOUT`. Used as a
+    /// fallback whenever a device isn't already in `connected_devices()` — which, for
+    /// Bluetooth accessories like earbuds, is the normal state.
+    async fn discover_devices(adapter: &Adapter) -> Result<Vec<Device>> {
+        let mut found = Vec::new();
+        let mut scan = adapter
+            .discover_devices(&[])
+            .await
+            .context("Failed to start Bluetooth discovery")?;
+        let mut timeout = Timer::after(DISCOVERY_TIMEOUT);
+
+        while let Some(Ok(device)) = future::or(async { scan.next().await }, async {
+            (&mut timeout).await;
+            None
+        })
+        .await
+        {
+            found.push(device);
+        }
+
+        Ok(found)
+    }
+
+    /// Resolve `address` to a `Device`, checking already-connected devices first and falling
+    /// back to a discovery scan for everything else.
+    async fn find_device(adapter: &Adapter, address: &str) -> Result<Device> {
+        if let Some(device) = adapter
+            .connected_devices()
+            .await?
+            .into_iter()
+            .find(|device| device.id().to_string() == address)
+        {
+            return Ok(device);
+        }
+
+        Self::discover_devices(adapter)
+            .await?
+            .into_iter()
+            .find(|device| device.id().to_string() == address)
+            .context("Device not found")
+    }
+
+    async fn read_battery_level(adapter: &Adapter, device: &Device) -> Option<i32> {
+        adapter.connect_device(device).await.ok()?;
+        let service = device
+            .discover_services_with_uuid(BATTERY_SERVICE)
+            .await
+            .ok()?
+            .into_iter()
+            .next()?;
+        let characteristic = service
+            .discover_characteristics_with_uuid(BATTERY_LEVEL)
+            .await
+            .ok()?
+            .into_iter()
+            .next()?;
+        let value = characteristic.read().await.ok()?;
+        value.first().map(|&level| level as i32)
+    }
+
+    async fn list_devices_async() -> Result<Vec<BluetoothDevice>> {
+        let adapter = Self::adapter().await?;
+        let mut devices: HashMap<String, BluetoothDevice> = HashMap::new();
+
+        for device in adapter.connected_devices().await?.into_iter() {
+            let battery = Self::read_battery_level(&adapter, &device).await;
+            let address = device.id().to_string();
+            devices.insert(
+                address.clone(),
+                BluetoothDevice {
+                    address,
+                    name: device.name().unwrap_or_else(|_| "Unknown".to_string()),
+                    connected: true,
+                    battery: battery.map(|single| BatteryInfo { left: None, right: None, single: Some(single) }),
+                },
+            );
+        }
+
+        // Fill in nearby devices found by a discovery scan (bonded or not) as not connected, so
+        // `list`/`watch`/`scan` also see devices that aren't connected. Bonded devices that are
+        // not advertising during the scan are not listed.
+        for device in Self::discover_devices(&adapter).await? {
+            let address = device.id().to_string();
+            devices.entry(address.clone()).or_insert_with(|| BluetoothDevice {
+                address,
+                name: device.name().unwrap_or_else(|_| "Unknown".to_string()),
+                connected: false,
+                battery: None,
+            });
+        }
+
+        Ok(devices.into_values().collect())
+    }
+}
+
+impl BluetoothBackend for BluestBackend {
+    fn list_devices(&self) -> Result<Vec<BluetoothDevice>> {
+        pollster::block_on(Self::list_devices_async())
+    }
+
+    fn connect(&self, address: &str) -> Result<()> {
+        pollster::block_on(async {
+            let adapter = Self::adapter().await?;
+            let device = Self::find_device(&adapter, address).await?;
+            adapter.connect_device(&device).await.context("Failed to connect to device")
+        })
+    }
+
+    fn disconnect(&self, address: &str) -> Result<()> {
+        pollster::block_on(async {
+            let adapter = Self::adapter().await?;
+            let device = Self::find_device(&adapter, address).await?;
+            adapter.disconnect_device(&device).await.context("Failed to disconnect device")
+        })
+    }
+
+    fn power_state(&self) -> Result<bool> {
+        pollster::block_on(async {
+            let Some(adapter) = Adapter::default().await else {
+                return Ok(false);
+            };
+            // `wait_available` resolves once the adapter is powered on and ready; there's no
+            // direct "is it on right now" query, so race it against a short timeout instead of
+            // blocking forever on an adapter that's off.
+            let available = future::or(async { adapter.wait_available().await.ok() }, async {
+                Timer::after(Duration::from_secs(2)).await;
+                None
+            })
+            .await;
+            Ok(available.is_some())
+        })
+    }
+}
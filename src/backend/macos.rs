@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::process::Command;
+
+use super::BluetoothBackend;
+use crate::{BatteryInfo, BluetoothDevice};
+
+/// Talks to the Bluetooth stack via the macOS command-line tools `blueutil` and
+/// `system_profiler`, the way bluepods always has.
+pub struct MacOsBackend;
+
+impl BluetoothBackend for MacOsBackend {
+    fn list_devices(&self) -> Result<Vec<BluetoothDevice>> {
+        let output = Command::new("system_profiler")
+            .args(["-json", "SPBluetoothDataType"])
+            .output()
+            .context("Failed to execute system_profiler command")?;
+
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        let json: Value = serde_json::from_str(&output_str).context("Failed to parse JSON output")?;
+
+        let mut devices = Vec::new();
+
+        // Helper function to process device entries
+        fn process_device_entry(entry: &Value, connected: bool) -> Option<BluetoothDevice> {
+            let (name, details) = entry.as_object()?.iter().next()?;
+
+            let address = details.get("device_address")?.as_str()?.to_string();
+
+            // Get battery information
+            let battery = Some(BatteryInfo {
+                left: details.get("device_batteryLevelLeft")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| s.trim_end_matches('%').parse().ok()),
+                right: details.get("device_batteryLevelRight")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| s.trim_end_matches('%').parse().ok()),
+                single: details.get("device_batteryLevel")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| s.trim_end_matches('%').parse().ok()),
+            });
+
+            Some(BluetoothDevice {
+                name: name.to_string(),
+                address,
+                connected,
+                battery,
+            })
+        }
+
+        // Process connected devices
+        if let Some(bluetooth_data) = json["SPBluetoothDataType"].get(0) {
+            if let Some(connected_devices) = bluetooth_data["device_connected"].as_array() {
+                for device in connected_devices {
+                    if let Some(device_info) = process_device_entry(device, true) {
+                        devices.push(device_info);
+                    }
+                }
+            }
+
+            // Process disconnected devices
+            if let Some(disconnected_devices) = bluetooth_data["device_not_connected"].as_array() {
+                for device in disconnected_devices {
+                    if let Some(device_info) = process_device_entry(device, false) {
+                        devices.push(device_info);
+                    }
+                }
+            }
+        }
+
+        Ok(devices)
+    }
+
+    fn connect(&self, address: &str) -> Result<()> {
+        let output = Command::new("blueutil")
+            .args(["--connect", address])
+            .output()
+            .context("Failed to connect to device")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "blueutil --connect failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    fn disconnect(&self, address: &str) -> Result<()> {
+        let output = Command::new("blueutil")
+            .args(["--disconnect", address])
+            .output()
+            .context("Failed to disconnect device")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "blueutil --disconnect failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    fn power_state(&self) -> Result<bool> {
+        let output = Command::new("blueutil")
+            .arg("--power")
+            .output()
+            .context("Failed to get Bluetooth power state")?;
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim() == "1")
+    }
+}
@@ -1,16 +1,42 @@
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{self, Write};
 use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
 use serde_json::Value;
 
+mod backend;
+use backend::BluetoothBackend;
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Output format for all commands
+    #[arg(long = "format", short = 'f', global = true, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable, colorized output
+    Text,
+    /// Machine-readable JSON, one document per command (`watch` prints one object per line)
+    Json,
+}
+
+impl OutputFormat {
+    fn is_json(self) -> bool {
+        matches!(self, OutputFormat::Json)
+    }
 }
 
 #[derive(Subcommand)]
@@ -23,149 +49,115 @@ enum Commands {
     Connect {
         /// Name of the device to connect to
         name: String,
+        /// Skip the interactive picker and auto-select the best match
+        #[arg(long = "yes", short = 'y')]
+        yes: bool,
     },
     /// Disconnect a Bluetooth device by name
     Disconnect {
         /// Name of the device to disconnect from
         name: String,
+        /// Skip the interactive picker and auto-select the best match
+        #[arg(long = "yes", short = 'y')]
+        yes: bool,
+    },
+    /// Watch for connect/disconnect and battery-level changes
+    Watch {
+        /// Polling interval in seconds
+        #[arg(long)]
+        interval: Option<u64>,
+        /// Fire a desktop notification when a device's battery drops below this percentage
+        #[arg(long)]
+        notify_below: Option<i32>,
+    },
+    /// Keep retrying a connection until the device appears or a timeout elapses
+    Reconnect {
+        /// Name of the device to reconnect to
+        name: String,
+        /// Give up after this many seconds (default 30)
+        timeout: Option<u64>,
+    },
+    /// Discover nearby devices, listed together with already-paired ones
+    Scan {
+        /// How long to scan for, in seconds (default 10)
+        timeout: Option<u64>,
+    },
+    /// Discover and pair with a nearby device by name
+    Pair {
+        /// Name of the device to pair with
+        name: String,
+        /// How long to scan for, in seconds (default 10)
+        timeout: Option<u64>,
     },
 }
 
-#[derive(Debug)]
-struct BatteryInfo {
-    left: Option<i32>,
-    right: Option<i32>,
-    single: Option<i32>,
+/// Minimum battery percentage change worth reporting while watching.
+const WATCH_BATTERY_THRESHOLD: i32 = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BatteryInfo {
+    pub(crate) left: Option<i32>,
+    pub(crate) right: Option<i32>,
+    pub(crate) single: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BluetoothDevice {
+    pub(crate) address: String,
+    pub(crate) name: String,
+    pub(crate) connected: bool,
+    pub(crate) battery: Option<BatteryInfo>,
 }
 
-#[derive(Debug)]
-struct BluetoothDevice {
+/// Result of a connect/disconnect action, for `--format json`.
+#[derive(Serialize)]
+struct ActionResult<'a> {
+    action: &'a str,
+    device: &'a str,
+    success: bool,
+}
+
+/// A device seen during a `scan`, merging nearby inquiry results with already-paired devices.
+#[derive(Debug, Clone, Serialize)]
+struct DiscoveredDevice {
     address: String,
     name: String,
-    connected: bool,
-    battery: Option<BatteryInfo>,
+    paired: bool,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let backend = backend::default_backend();
 
     match cli.command {
-        Commands::Status => show_status()?,
-        Commands::List => list_devices()?,
-        Commands::Connect { name } => connect_to_device(&name)?,
-        Commands::Disconnect { name } => disconnect_device(&name)?,
+        Commands::Status => show_status(&backend, cli.format)?,
+        Commands::List => list_devices(&backend, cli.format)?,
+        Commands::Connect { name, yes } => connect_to_device(&backend, &name, yes, cli.format)?,
+        Commands::Disconnect { name, yes } => disconnect_device(&backend, &name, yes, cli.format)?,
+        Commands::Watch { interval, notify_below } => watch_devices(&backend, interval, notify_below, cli.format)?,
+        Commands::Reconnect { name, timeout } => reconnect_device(&backend, &name, timeout, cli.format)?,
+        Commands::Scan { timeout } => scan_devices(&backend, timeout, cli.format)?,
+        Commands::Pair { name, timeout } => pair_device(&name, timeout, cli.format)?,
     }
 
     Ok(())
 }
 
-fn get_device_battery(name: &str) -> Option<i32> {
-    // Try to get battery information using ioreg
-    let output = Command::new("ioreg")
-        .args(["-r", "-k", "BatteryPercent", "-c", "AppleDeviceModel"])
-        .output()
-        .ok()?;
-
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    
-    // Find the device section
-    for section in output_str.split("+-o") {
-        if section.contains(name) {
-            // Try to find battery percentage
-            if let Some(battery_line) = section.lines().find(|line| line.contains("\"BatteryPercent\" = ")) {
-                if let Some(percent_str) = battery_line.split('=').nth(1) {
-                    if let Ok(percent) = percent_str.trim().parse::<i32>() {
-                        return Some(percent);
-                    }
-                }
-            }
-        }
-    }
-    None
-}
-
-fn get_devices_with_battery() -> Result<Vec<BluetoothDevice>> {
-    let output = Command::new("system_profiler")
-        .args(["-json", "SPBluetoothDataType"])
-        .output()
-        .context("Failed to execute system_profiler command")?;
-
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let json: Value = serde_json::from_str(&output_str)
-        .context("Failed to parse JSON output")?;
-
-    let mut devices = Vec::new();
-
-    // Helper function to process device entries
-    fn process_device_entry(entry: &Value, connected: bool) -> Option<BluetoothDevice> {
-        let (name, details) = entry.as_object()?.iter().next()?;
-        
-        let address = details.get("device_address")?.as_str()?.to_string();
-        
-        // Get battery information
-        let battery = Some(BatteryInfo {
-            left: details.get("device_batteryLevelLeft")
-                .and_then(|v| v.as_str())
-                .and_then(|s| s.trim_end_matches('%').parse().ok()),
-            right: details.get("device_batteryLevelRight")
-                .and_then(|v| v.as_str())
-                .and_then(|s| s.trim_end_matches('%').parse().ok()),
-            single: details.get("device_batteryLevel")
-                .and_then(|v| v.as_str())
-                .and_then(|s| s.trim_end_matches('%').parse().ok()),
-        });
-
-        Some(BluetoothDevice {
-            name: name.to_string(),
-            address,
-            connected,
-            battery,
-        })
-    }
-
-    // Process connected devices
-    if let Some(bluetooth_data) = json["SPBluetoothDataType"].get(0) {
-        if let Some(connected_devices) = bluetooth_data["device_connected"].as_array() {
-            for device in connected_devices {
-                if let Some(device_info) = process_device_entry(device, true) {
-                    devices.push(device_info);
-                }
-            }
-        }
-
-        // Process disconnected devices
-        if let Some(disconnected_devices) = bluetooth_data["device_not_connected"].as_array() {
-            for device in disconnected_devices {
-                if let Some(device_info) = process_device_entry(device, false) {
-                    devices.push(device_info);
-                }
-            }
-        }
-    }
-
-    Ok(devices)
-}
-
-fn get_bluetooth_power() -> Result<bool> {
-    let output = Command::new("blueutil")
-        .arg("--power")
-        .output()
-        .context("Failed to get Bluetooth power state")?;
-    
-    let power = String::from_utf8_lossy(&output.stdout).trim() == "1";
-    Ok(power)
-}
-
+/// Only macOS exposes a discoverable toggle via `blueutil`; other backends have no
+/// equivalent yet, so `show_status` treats this as unknown rather than calling it at all.
+#[cfg(target_os = "macos")]
 fn get_discoverable() -> Result<bool> {
     let output = Command::new("blueutil")
         .arg("--discoverable")
         .output()
         .context("Failed to get discoverable state")?;
-    
+
     let discoverable = String::from_utf8_lossy(&output.stdout).trim() == "1";
     Ok(discoverable)
 }
 
+/// Only macOS can report the default audio output device this way.
+#[cfg(target_os = "macos")]
 fn get_default_output_device() -> Result<Option<String>> {
     let output = Command::new("system_profiler")
         .args(["SPAudioDataType", "-json"])
@@ -200,23 +192,59 @@ fn get_battery_color(percentage: i32) -> colored::Color {
     }
 }
 
-fn format_battery_percentage(percentage: i32) -> ColoredString {
-    format!("{}%", percentage).color(get_battery_color(percentage))
+/// Map a capacity percentage to a five-tier battery glyph, i3status-rs style.
+fn battery_icon(percentage: i32) -> &'static str {
+    match percentage {
+        81..=100 => "▰▰▰▰▰",
+        61..=80 => "▰▰▰▰▱",
+        41..=60 => "▰▰▰▱▱",
+        21..=40 => "▰▰▱▱▱",
+        _ => "▰▱▱▱▱",
+    }
 }
 
-fn show_status() -> Result<()> {
-    // Get Bluetooth power state
-    let power = get_bluetooth_power()?;
+/// Battery percentage with its level glyph, colored via `get_battery_color`.
+///
+/// `charging` is currently always `false`: `system_profiler`'s Bluetooth data doesn't expose a
+/// charging signal for paired accessories, so there's nothing to report yet.
+fn format_battery_with_icon(percentage: i32, charging: bool) -> ColoredString {
+    let marker = if charging { " ⚡" } else { "" };
+    format!("{} {}%{}", battery_icon(percentage), percentage, marker)
+        .color(get_battery_color(percentage))
+}
+
+fn show_status(backend: &dyn BluetoothBackend, format: OutputFormat) -> Result<()> {
+    let power = backend.power_state()?;
+    let devices = backend.list_devices()?;
+
+    #[cfg(target_os = "macos")]
+    let discoverable = get_discoverable().ok();
+    #[cfg(not(target_os = "macos"))]
+    let discoverable: Option<bool> = None;
+
+    #[cfg(target_os = "macos")]
+    let output_device = get_default_output_device().ok().flatten();
+    #[cfg(not(target_os = "macos"))]
+    let output_device: Option<String> = None;
+
+    if format.is_json() {
+        let doc = serde_json::json!({
+            "power": power,
+            "discoverable": discoverable,
+            "default_output": output_device,
+            "devices": devices,
+        });
+        println!("{}", serde_json::to_string(&doc)?);
+        return Ok(());
+    }
+
     let power_status = if power { "On".green() } else { "Off".red() };
     println!("Bluetooth:        {}", power_status);
 
-    // Get default audio output
-    if let Ok(Some(output_device)) = get_default_output_device() {
+    if let Some(output_device) = output_device {
         println!("Default Output:   {}", output_device);
     }
 
-    // Get paired devices with battery info
-    let devices = get_devices_with_battery()?;
     println!("\nPaired Devices:");
     for device in devices {
         let status = if device.connected {
@@ -224,15 +252,15 @@ fn show_status() -> Result<()> {
         } else {
             "not connected".red()
         };
-        
+
         let battery_info = match device.battery {
             Some(battery) => {
                 if let (Some(left), Some(right)) = (battery.left, battery.right) {
-                    format!(", battery: L:{} R:{}", 
-                        format_battery_percentage(left),
-                        format_battery_percentage(right))
+                    format!(", battery: L:{} R:{}",
+                        format_battery_with_icon(left, false),
+                        format_battery_with_icon(right, false))
                 } else if let Some(single) = battery.single {
-                    format!(", battery: {}", format_battery_percentage(single))
+                    format!(", battery: {}", format_battery_with_icon(single, false))
                 } else {
                     String::new()
                 }
@@ -240,24 +268,31 @@ fn show_status() -> Result<()> {
             None => String::new(),
         };
 
-        println!("  - {:<25} ({}{})", 
+        println!("  - {:<25} ({}{})",
             device.name,
             status,
             battery_info
         );
     }
 
-    // Get discoverable state
-    let discoverable = get_discoverable()?;
-    println!("\nSystem Discoverable: {}", if discoverable { "Yes".green() } else { "No".red() });
+    match discoverable {
+        Some(true) => println!("\nSystem Discoverable: {}", "Yes".green()),
+        Some(false) => println!("\nSystem Discoverable: {}", "No".red()),
+        None => println!("\nSystem Discoverable: {}", "n/a".dimmed()),
+    }
 
     Ok(())
 }
 
-fn list_devices() -> Result<()> {
-    let devices = get_devices_with_battery()?;
+fn list_devices(backend: &dyn BluetoothBackend, format: OutputFormat) -> Result<()> {
+    let devices = backend.list_devices()?;
+
+    if format.is_json() {
+        println!("{}", serde_json::to_string(&devices)?);
+        return Ok(());
+    }
+
     println!("Paired devices:");
-    
     for device in devices {
         let status = if device.connected {
             "Connected".green()
@@ -270,56 +305,276 @@ fn list_devices() -> Result<()> {
     Ok(())
 }
 
-fn connect_to_device(search_name: &str) -> Result<()> {
-    let devices = get_devices_with_battery()?;
+/// Parse `address: ..., name: "..."` pairs out of `blueutil`'s device listing output
+/// (shared by `--inquiry`, `--paired`, and similar listing flags).
+fn parse_blueutil_devices(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let address = line.split("address: ").nth(1)?.split(',').next()?.trim().to_string();
+            let name = line
+                .split("name: \"")
+                .nth(1)
+                .and_then(|s| s.split('"').next())
+                .unwrap_or("Unknown")
+                .to_string();
+            Some((address, name))
+        })
+        .collect()
+}
+
+fn scan_devices(backend: &dyn BluetoothBackend, timeout: Option<u64>, format: OutputFormat) -> Result<()> {
+    let timeout = timeout.unwrap_or(10);
+    let output = Command::new("blueutil")
+        .args(["--inquiry", &timeout.to_string()])
+        .output()
+        .context("Failed to run blueutil --inquiry")?;
+    let output_str = String::from_utf8_lossy(&output.stdout);
+
+    let mut devices: HashMap<String, DiscoveredDevice> = parse_blueutil_devices(&output_str)
+        .into_iter()
+        .map(|(address, name)| (address.clone(), DiscoveredDevice { address, name, paired: false }))
+        .collect();
+
+    for paired in backend.list_devices()? {
+        devices
+            .entry(paired.address.clone())
+            .and_modify(|d| d.paired = true)
+            .or_insert(DiscoveredDevice {
+                address: paired.address,
+                name: paired.name,
+                paired: true,
+            });
+    }
+
+    let mut devices: Vec<_> = devices.into_values().collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if format.is_json() {
+        println!("{}", serde_json::to_string(&devices)?);
+        return Ok(());
+    }
+
+    println!("Discovered devices:");
+    for device in devices {
+        let tag = if device.paired { "paired".green() } else { "new".yellow() };
+        println!("  {} ({}) \"{}\"", device.address, tag, device.name);
+    }
+
+    Ok(())
+}
+
+fn pair_device(search_name: &str, timeout: Option<u64>, format: OutputFormat) -> Result<()> {
+    let timeout = timeout.unwrap_or(10);
+    let output = Command::new("blueutil")
+        .args(["--inquiry", &timeout.to_string()])
+        .output()
+        .context("Failed to run blueutil --inquiry")?;
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let discovered = parse_blueutil_devices(&output_str);
+
     let matcher = SkimMatcherV2::default();
-    
-    let mut matches: Vec<_> = devices
+    let mut matches: Vec<_> = discovered
         .iter()
-        .filter_map(|device| {
+        .filter_map(|(address, name)| {
             matcher
-                .fuzzy_match(&device.name.to_lowercase(), &search_name.to_lowercase())
-                .map(|score| (device, score))
+                .fuzzy_match(&name.to_lowercase(), &search_name.to_lowercase())
+                .map(|score| (address, name, score))
         })
         .collect();
+    matches.sort_by_key(|(_, _, score)| -score);
+
+    let (address, name) = match matches.first() {
+        Some((address, name, _)) => ((*address).clone(), (*name).clone()),
+        None => {
+            if format.is_json() {
+                println!(
+                    "{}",
+                    serde_json::to_string(&ActionResult {
+                        action: "pair",
+                        device: search_name,
+                        success: false,
+                    })?
+                );
+            } else {
+                println!("No discoverable devices found matching '{}'", search_name);
+            }
+            return Ok(());
+        }
+    };
 
-    matches.sort_by_key(|(_, score)| -score);
+    if !format.is_json() {
+        println!("Pairing with {}...", name);
+    }
 
-    match matches.len() {
-        0 => println!("No devices found matching '{}'", search_name),
-        1 => {
-            let device = matches[0].0;
-            println!("Connecting to {}...", device.name);
-            Command::new("blueutil")
-                .args(["--connect", &device.address])
-                .output()
-                .context("Failed to connect to device")?;
-            println!("Connected successfully!");
+    let output = Command::new("blueutil")
+        .args(["--pair", &address])
+        .output()
+        .context("Failed to run blueutil --pair")?;
+    let success = output.status.success();
+
+    if format.is_json() {
+        println!(
+            "{}",
+            serde_json::to_string(&ActionResult {
+                action: "pair",
+                device: &name,
+                success,
+            })?
+        );
+    } else if success {
+        println!("Paired successfully!");
+    } else {
+        println!("Failed to pair with {}", name);
+    }
+
+    Ok(())
+}
+
+/// Emit one watch transition, in text or NDJSON form depending on `format`.
+fn emit_watch_event(format: OutputFormat, event: &str, device: &str, detail: &str, json_extra: Value) {
+    if format.is_json() {
+        let mut doc = serde_json::json!({
+            "event": event,
+            "device": device,
+        });
+        if let (Some(obj), Some(extra)) = (doc.as_object_mut(), json_extra.as_object()) {
+            obj.extend(extra.clone());
+        }
+        println!("{}", doc);
+    } else {
+        println!("{} {}", device, detail);
+    }
+}
+
+fn watch_devices(
+    backend: &dyn BluetoothBackend,
+    interval: Option<u64>,
+    notify_below: Option<i32>,
+    format: OutputFormat,
+) -> Result<()> {
+    let interval = Duration::from_secs(interval.unwrap_or(3));
+    let mut last_seen: HashMap<String, BluetoothDevice> = HashMap::new();
+    let mut notified_below: HashMap<String, i32> = HashMap::new();
+
+    loop {
+        let devices = backend.list_devices()?;
+
+        for device in &devices {
+            match last_seen.get(&device.address) {
+                None => {
+                    // First time we've seen this address; nothing to diff against yet.
+                }
+                Some(previous) => {
+                    if previous.connected != device.connected {
+                        let event = if device.connected { "connected" } else { "disconnected" };
+                        let colored_event = if device.connected {
+                            event.green()
+                        } else {
+                            event.red()
+                        };
+                        emit_watch_event(
+                            format,
+                            event,
+                            &device.name,
+                            &format!("{}", colored_event),
+                            Value::Null,
+                        );
+                    }
+
+                    for (side, prev, curr) in battery_sides(previous, device) {
+                        if let (Some(prev), Some(curr)) = (prev, curr) {
+                            if (prev - curr).abs() >= WATCH_BATTERY_THRESHOLD {
+                                emit_watch_event(
+                                    format,
+                                    "battery",
+                                    &device.name,
+                                    &format!("battery ({}) {}% -> {}%", side, prev, curr),
+                                    serde_json::json!({ "side": side, "from": prev, "to": curr }),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(threshold) = notify_below {
+                maybe_notify_low_battery(device, threshold, &mut notified_below);
+            }
         }
-        _ => {
-            println!("Multiple devices found. Please choose one:");
-            for (i, (device, _)) in matches.iter().enumerate() {
-                println!("{}. {}", i + 1, device.name);
+
+        last_seen = devices.into_iter().map(|d| (d.address.clone(), d)).collect();
+        thread::sleep(interval);
+    }
+}
+
+/// Fire a native notification the first time a device's level drops below `threshold`,
+/// then stay quiet until the level recovers, so a single low-battery event doesn't spam.
+fn maybe_notify_low_battery(device: &BluetoothDevice, threshold: i32, notified_below: &mut HashMap<String, i32>) {
+    let Some(battery) = &device.battery else { return };
+
+    for (side, level) in [("left", battery.left), ("right", battery.right), ("single", battery.single)] {
+        let Some(level) = level else { continue };
+        let key = format!("{}:{}", device.address, side);
+
+        if level < threshold {
+            if let std::collections::hash_map::Entry::Vacant(entry) = notified_below.entry(key) {
+                let title = format!("{} low battery", device.name);
+                let message = if side == "single" {
+                    format!("{}% remaining", level)
+                } else {
+                    format!("{} earbud at {}%", side, level)
+                };
+                let _ = send_macos_notification(&title, &message);
+                entry.insert(level);
             }
-            // In a real implementation, you would handle user input here
-            // For now, we'll just connect to the best match
-            let device = matches[0].0;
-            println!("Connecting to best match: {}...", device.name);
-            Command::new("blueutil")
-                .args(["--connect", &device.address])
-                .output()
-                .context("Failed to connect to device")?;
-            println!("Connected successfully!");
+        } else {
+            notified_below.remove(&key);
         }
     }
+}
 
+/// Fire a native macOS notification via `osascript`.
+fn send_macos_notification(title: &str, message: &str) -> Result<()> {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let script = format!(
+        "display notification \"{}\" with title \"{}\"",
+        escape(message),
+        escape(title)
+    );
+    Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .context("Failed to display notification")?;
     Ok(())
 }
 
-fn disconnect_device(search_name: &str) -> Result<()> {
-    let devices = get_devices_with_battery()?;
+/// Pair up (left, right, single) battery readings between two snapshots of the same device.
+fn battery_sides<'a>(
+    previous: &'a BluetoothDevice,
+    current: &'a BluetoothDevice,
+) -> Vec<(&'static str, Option<i32>, Option<i32>)> {
+    let prev = previous.battery.as_ref();
+    let curr = current.battery.as_ref();
+    vec![
+        ("left", prev.and_then(|b| b.left), curr.and_then(|b| b.left)),
+        ("right", prev.and_then(|b| b.right), curr.and_then(|b| b.right)),
+        ("single", prev.and_then(|b| b.single), curr.and_then(|b| b.single)),
+    ]
+}
+
+/// Fuzzy-resolve `search_name` against `devices`, sorted by match score.
+///
+/// With a single match (or `interactive` off), the best match is returned immediately. With
+/// multiple matches and `interactive` on, the candidates are printed and a selection is read
+/// from stdin, accepting either the index or an empty line for the top match.
+fn resolve_device<'a>(
+    devices: &'a [BluetoothDevice],
+    search_name: &str,
+    interactive: bool,
+) -> Result<Option<&'a BluetoothDevice>> {
     let matcher = SkimMatcherV2::default();
-    
+
     let mut matches: Vec<_> = devices
         .iter()
         .filter_map(|device| {
@@ -331,33 +586,334 @@ fn disconnect_device(search_name: &str) -> Result<()> {
 
     matches.sort_by_key(|(_, score)| -score);
 
-    match matches.len() {
-        0 => println!("No devices found matching '{}'", search_name),
-        1 => {
-            let device = matches[0].0;
-            println!("Disconnecting from {}...", device.name);
-            Command::new("blueutil")
-                .args(["--disconnect", &device.address])
-                .output()
-                .context("Failed to disconnect device")?;
-            println!("Disconnected successfully!");
+    if matches.len() <= 1 || !interactive {
+        return Ok(matches.first().map(|(device, _)| *device));
+    }
+
+    println!("Multiple devices found. Please choose one:");
+    for (i, (device, _)) in matches.iter().enumerate() {
+        println!("{}. {}", i + 1, device.name);
+    }
+    print!("Enter a number (default 1): ");
+    io::stdout().flush().ok();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).context("Failed to read selection")?;
+
+    let index = match parse_selection(&input, matches.len()) {
+        Ok(index) => index,
+        Err(()) => {
+            println!("Invalid selection, using best match: {}", matches[0].0.name);
+            0
         }
-        _ => {
-            println!("Multiple devices found. Please choose one:");
-            for (i, (device, _)) in matches.iter().enumerate() {
-                println!("{}. {}", i + 1, device.name);
+    };
+
+    Ok(Some(matches[index].0))
+}
+
+/// Parse a user's raw selection line against `candidate_count` numbered options.
+///
+/// Empty input (just pressing enter) picks the top match. Anything else that doesn't parse
+/// to an in-range option number is rejected so the caller can fall back and say why.
+fn parse_selection(input: &str, candidate_count: usize) -> Result<usize, ()> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Ok(0);
+    }
+
+    match trimmed.parse::<usize>() {
+        Ok(choice) if (1..=candidate_count).contains(&choice) => Ok(choice - 1),
+        _ => Err(()),
+    }
+}
+
+fn connect_to_device(
+    backend: &dyn BluetoothBackend,
+    search_name: &str,
+    yes: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let devices = backend.list_devices()?;
+
+    let device = match resolve_device(&devices, search_name, !yes && !format.is_json())? {
+        Some(device) => device,
+        None => {
+            if format.is_json() {
+                println!(
+                    "{}",
+                    serde_json::to_string(&ActionResult {
+                        action: "connect",
+                        device: search_name,
+                        success: false,
+                    })?
+                );
+            } else {
+                println!("No devices found matching '{}'", search_name);
             }
-            // In a real implementation, you would handle user input here
-            // For now, we'll just disconnect the best match
-            let device = matches[0].0;
-            println!("Disconnecting from best match: {}...", device.name);
-            Command::new("blueutil")
-                .args(["--disconnect", &device.address])
-                .output()
-                .context("Failed to disconnect device")?;
-            println!("Disconnected successfully!");
+            return Ok(());
         }
+    };
+
+    if !format.is_json() {
+        println!("Connecting to {}...", device.name);
+    }
+    backend.connect(&device.address)?;
+    if format.is_json() {
+        println!(
+            "{}",
+            serde_json::to_string(&ActionResult {
+                action: "connect",
+                device: &device.name,
+                success: true,
+            })?
+        );
+    } else {
+        println!("Connected successfully!");
     }
 
     Ok(())
+}
+
+/// Check whether a paired device at `address` currently reports as connected.
+fn device_connected(backend: &dyn BluetoothBackend, address: &str) -> Result<bool> {
+    let devices = backend.list_devices()?;
+    Ok(devices.iter().any(|d| d.address == address && d.connected))
+}
+
+fn reconnect_device(
+    backend: &dyn BluetoothBackend,
+    search_name: &str,
+    timeout: Option<u64>,
+    format: OutputFormat,
+) -> Result<()> {
+    let devices = backend.list_devices()?;
+
+    let device = match resolve_device(&devices, search_name, false)? {
+        Some(device) => device,
+        None => {
+            if format.is_json() {
+                println!(
+                    "{}",
+                    serde_json::to_string(&ActionResult {
+                        action: "reconnect",
+                        device: search_name,
+                        success: false,
+                    })?
+                );
+            } else {
+                println!("No devices found matching '{}'", search_name);
+            }
+            return Ok(());
+        }
+    };
+
+    let timeout = Duration::from_secs(timeout.unwrap_or(30));
+    let start = Instant::now();
+    let mut backoff = Duration::from_secs(1);
+    let mut attempt = 0u32;
+
+    loop {
+        attempt += 1;
+        // A failed connect attempt is expected while the device is out of range or asleep;
+        // keep retrying with backoff instead of aborting on the first one.
+        let _ = backend.connect(&device.address);
+
+        if device_connected(backend, &device.address)? {
+            if format.is_json() {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "action": "reconnect",
+                        "device": device.name,
+                        "success": true,
+                        "attempts": attempt,
+                    })
+                );
+            } else {
+                println!("Reconnected to {} after {} attempt(s).", device.name, attempt);
+            }
+            return Ok(());
+        }
+
+        if start.elapsed() >= timeout {
+            if format.is_json() {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "action": "reconnect",
+                        "device": device.name,
+                        "success": false,
+                        "attempts": attempt,
+                    })
+                );
+            }
+            anyhow::bail!(
+                "Timed out reconnecting to {} after {} attempt(s)",
+                device.name,
+                attempt
+            );
+        }
+
+        thread::sleep(backoff);
+        backoff = (backoff * 2).min(Duration::from_secs(10));
+    }
+}
+
+fn disconnect_device(
+    backend: &dyn BluetoothBackend,
+    search_name: &str,
+    yes: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let devices = backend.list_devices()?;
+
+    let device = match resolve_device(&devices, search_name, !yes && !format.is_json())? {
+        Some(device) => device,
+        None => {
+            if format.is_json() {
+                println!(
+                    "{}",
+                    serde_json::to_string(&ActionResult {
+                        action: "disconnect",
+                        device: search_name,
+                        success: false,
+                    })?
+                );
+            } else {
+                println!("No devices found matching '{}'", search_name);
+            }
+            return Ok(());
+        }
+    };
+
+    if !format.is_json() {
+        println!("Disconnecting from {}...", device.name);
+    }
+    backend.disconnect(&device.address)?;
+    if format.is_json() {
+        println!(
+            "{}",
+            serde_json::to_string(&ActionResult {
+                action: "disconnect",
+                device: &device.name,
+                success: true,
+            })?
+        );
+    } else {
+        println!("Disconnected successfully!");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(address: &str, battery: Option<BatteryInfo>) -> BluetoothDevice {
+        BluetoothDevice {
+            address: address.to_string(),
+            name: address.to_string(),
+            connected: true,
+            battery,
+        }
+    }
+
+    #[test]
+    fn battery_sides_pairs_up_matching_readings() {
+        let previous = device(
+            "aa",
+            Some(BatteryInfo { left: Some(80), right: Some(60), single: None }),
+        );
+        let current = device(
+            "aa",
+            Some(BatteryInfo { left: Some(70), right: None, single: Some(50) }),
+        );
+
+        let sides = battery_sides(&previous, &current);
+
+        assert_eq!(sides, vec![
+            ("left", Some(80), Some(70)),
+            ("right", Some(60), None),
+            ("single", None, Some(50)),
+        ]);
+    }
+
+    #[test]
+    fn battery_sides_handles_no_battery_info() {
+        let previous = device("aa", None);
+        let current = device("aa", None);
+
+        let sides = battery_sides(&previous, &current);
+
+        assert_eq!(sides, vec![
+            ("left", None, None),
+            ("right", None, None),
+            ("single", None, None),
+        ]);
+    }
+
+    #[test]
+    fn battery_icon_covers_each_tier_boundary() {
+        assert_eq!(battery_icon(100), "▰▰▰▰▰");
+        assert_eq!(battery_icon(81), "▰▰▰▰▰");
+        assert_eq!(battery_icon(80), "▰▰▰▰▱");
+        assert_eq!(battery_icon(61), "▰▰▰▰▱");
+        assert_eq!(battery_icon(60), "▰▰▰▱▱");
+        assert_eq!(battery_icon(41), "▰▰▰▱▱");
+        assert_eq!(battery_icon(40), "▰▰▱▱▱");
+        assert_eq!(battery_icon(21), "▰▰▱▱▱");
+        assert_eq!(battery_icon(20), "▰▱▱▱▱");
+        assert_eq!(battery_icon(0), "▰▱▱▱▱");
+    }
+
+    #[test]
+    fn parse_blueutil_devices_extracts_address_and_name() {
+        let output = "address: 00-11-22-33-44-55, not connected, not favourite, paired, name: \"AirPods Pro\"\n\
+                       address: 66-77-88-99-aa-bb, name: \"Magic Keyboard\"";
+
+        let devices = parse_blueutil_devices(output);
+
+        assert_eq!(devices, vec![
+            ("00-11-22-33-44-55".to_string(), "AirPods Pro".to_string()),
+            ("66-77-88-99-aa-bb".to_string(), "Magic Keyboard".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn parse_blueutil_devices_defaults_name_when_missing() {
+        let output = "address: 00-11-22-33-44-55, not connected";
+
+        let devices = parse_blueutil_devices(output);
+
+        assert_eq!(devices, vec![("00-11-22-33-44-55".to_string(), "Unknown".to_string())]);
+    }
+
+    #[test]
+    fn parse_blueutil_devices_skips_lines_without_an_address() {
+        let output = "Some banner line\naddress: 00-11-22-33-44-55, name: \"AirPods Pro\"";
+
+        let devices = parse_blueutil_devices(output);
+
+        assert_eq!(devices, vec![("00-11-22-33-44-55".to_string(), "AirPods Pro".to_string())]);
+    }
+
+    #[test]
+    fn parse_selection_empty_input_picks_top_match() {
+        assert_eq!(parse_selection("\n", 3), Ok(0));
+        assert_eq!(parse_selection("   ", 3), Ok(0));
+    }
+
+    #[test]
+    fn parse_selection_accepts_in_range_choice() {
+        assert_eq!(parse_selection("1", 3), Ok(0));
+        assert_eq!(parse_selection("3", 3), Ok(2));
+    }
+
+    #[test]
+    fn parse_selection_rejects_out_of_range_or_non_numeric() {
+        assert_eq!(parse_selection("0", 3), Err(()));
+        assert_eq!(parse_selection("4", 3), Err(()));
+        assert_eq!(parse_selection("nope", 3), Err(()));
+    }
 } 
\ No newline at end of file